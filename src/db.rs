@@ -0,0 +1,60 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// One finished run, appended to the history file so progress carries across sessions.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    pub score: usize,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub duration_secs: u64,
+    pub mode: String,
+}
+
+fn db_path() -> PathBuf {
+    let mut path = dirs::home_dir().unwrap_or_default();
+    path.push(".config");
+    path.push("tui-typing-test");
+    path.push("history.json");
+    path
+}
+
+/// Loads the run history, treating a missing file as an empty history rather than an error.
+pub fn load_history() -> Result<Vec<RunRecord>, Error> {
+    let path = db_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)?;
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let records: Vec<RunRecord> = serde_json::from_str(&contents)?;
+    Ok(records)
+}
+
+/// Appends `record` to the history file, creating it (and its parent dir) if missing.
+pub fn append_run(record: RunRecord) -> Result<Vec<RunRecord>, Error> {
+    let path = db_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut records = load_history()?;
+    records.push(record);
+
+    let serialized = serde_json::to_string_pretty(&records)?;
+    fs::write(&path, serialized)?;
+
+    Ok(records)
+}
+
+/// The highest score across all recorded runs, if any.
+pub fn best_score(history: &[RunRecord]) -> Option<usize> {
+    history.iter().map(|r| r.score).max()
+}
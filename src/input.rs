@@ -0,0 +1,116 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A typed-input buffer edited by grapheme cluster rather than by byte or `char`, so
+/// accented letters and other multi-codepoint glyphs survive backspace/word-delete intact.
+#[derive(Default)]
+pub struct InputBuffer {
+    text: String,
+}
+
+impl InputBuffer {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Number of graphemes typed so far, used to index into the target word.
+    pub fn grapheme_count(&self) -> usize {
+        self.text.graphemes(true).count()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    /// Backspace: deletes the previous grapheme cluster, not just the previous byte/char.
+    pub fn delete_grapheme(&mut self) {
+        if let Some((idx, _)) = self.text.grapheme_indices(true).last() {
+            self.text.truncate(idx);
+        }
+    }
+
+    /// Ctrl+W / Alt+Backspace: deletes back to the start of the previous whitespace-delimited word.
+    pub fn delete_word(&mut self) {
+        let trimmed_len = self.text.trim_end_matches(char::is_whitespace).len();
+        let trimmed = &self.text[..trimmed_len];
+        let cut = trimmed.rfind(char::is_whitespace).map(|idx| idx + 1).unwrap_or(0);
+        self.text.truncate(cut);
+    }
+
+    /// Ctrl+U: clears the buffer back to the start.
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "é" as an unprecomposed grapheme: 'e' followed by a combining acute accent (U+0301).
+    const COMBINING_E_ACUTE: char = '\u{0301}';
+
+    #[test]
+    fn grapheme_count_treats_a_combining_mark_as_one_grapheme() {
+        let mut buf = InputBuffer::default();
+        buf.push('e');
+        buf.push(COMBINING_E_ACUTE);
+        assert_eq!(buf.grapheme_count(), 1);
+    }
+
+    #[test]
+    fn delete_grapheme_removes_a_whole_multi_codepoint_grapheme() {
+        let mut buf = InputBuffer::default();
+        buf.push('h');
+        buf.push('e');
+        buf.push(COMBINING_E_ACUTE);
+        buf.delete_grapheme();
+        assert_eq!(buf.as_str(), "h");
+        assert_eq!(buf.grapheme_count(), 1);
+    }
+
+    #[test]
+    fn delete_grapheme_on_an_empty_buffer_is_a_no_op() {
+        let mut buf = InputBuffer::default();
+        buf.delete_grapheme();
+        assert_eq!(buf.as_str(), "");
+    }
+
+    #[test]
+    fn delete_word_drops_the_last_whitespace_delimited_word() {
+        let mut buf = InputBuffer::default();
+        for c in "foo bar".chars() {
+            buf.push(c);
+        }
+        buf.delete_word();
+        assert_eq!(buf.as_str(), "foo ");
+    }
+
+    #[test]
+    fn delete_word_ignores_trailing_whitespace_before_cutting() {
+        let mut buf = InputBuffer::default();
+        for c in "foo bar  ".chars() {
+            buf.push(c);
+        }
+        buf.delete_word();
+        assert_eq!(buf.as_str(), "foo ");
+    }
+
+    #[test]
+    fn delete_word_on_a_single_word_clears_the_buffer() {
+        let mut buf = InputBuffer::default();
+        for c in "foo".chars() {
+            buf.push(c);
+        }
+        buf.delete_word();
+        assert_eq!(buf.as_str(), "");
+    }
+
+    #[test]
+    fn clear_empties_the_buffer() {
+        let mut buf = InputBuffer::default();
+        buf.push('x');
+        buf.clear();
+        assert_eq!(buf.as_str(), "");
+        assert_eq!(buf.grapheme_count(), 0);
+    }
+}
@@ -1,7 +1,9 @@
 
 use crossterm::{
+    cursor::Show,
     event::{self, Event as CEvent, KeyCode, KeyModifiers},
-    terminal::{disable_raw_mode, enable_raw_mode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
 
@@ -11,18 +13,42 @@ use std::thread;
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tui::{
-    backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
+    backend::{Backend, CrosstermBackend},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
     widgets::{
         Block, BorderType, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, Tabs, Gauge,
     },
-    Terminal,
+    Frame, Terminal,
 };
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use argh::FromArgs;
 use rand_word::new;
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
+use unicode_segmentation::UnicodeSegmentation;
+
+mod db;
+mod input;
+mod mode;
+mod practice;
+mod stats;
+mod wordlist;
+use db::RunRecord;
+use input::InputBuffer;
+use mode::Mode;
+use stats::TypingStats;
+use wordlist::WordList;
+
+/// A terminal typing test with timed, word-count, quote, and spaced-repetition practice modes.
+#[derive(FromArgs)]
+struct Args {
+    /// plain-text word/quote list (one entry per line) to draw words from instead of random English words
+    #[argh(option)]
+    wordlist: Option<PathBuf>,
+}
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -41,6 +67,7 @@ enum Event<I> {
 enum MenuItem {
     Home,
     Game,
+    Practice,
     GameOver,
 }
 
@@ -49,13 +76,53 @@ impl From<MenuItem> for usize {
         match input {
             MenuItem::Home => 0,
             MenuItem::Game => 1,
-            MenuItem::GameOver => 2,
+            MenuItem::Practice => 2,
+            MenuItem::GameOver => 3,
         }
     }
 }
 
+// Undoes enable_raw_mode()/EnterAlternateScreen, best-effort since this also runs from the panic hook.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+}
+
+// Chains onto the default panic hook so a panic mid-game doesn't leave the shell in raw mode.
+fn install_panic_hook() {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        restore_terminal();
+        original_hook(panic_info);
+    }));
+}
+
+// RAII guard pairing terminal setup with teardown, so every early return still restores the shell.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    enable_raw_mode().expect("can run in raw mode");
+    let args: Args = argh::from_env();
+    let mut custom_words: Option<WordList> = match &args.wordlist {
+        Some(path) => Some(WordList::load(path)?),
+        None => None,
+    };
+
+    install_panic_hook();
+    let _terminal_guard = TerminalGuard::new().expect("can run in raw mode");
 
     let (tx, rx) = mpsc::channel();
     let tick_rate = Duration::from_millis(200);
@@ -86,16 +153,34 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let menu_titles = vec!["Start", "Quit"];
-    let mut curr_input = String::from("");
+    let menu_titles = vec!["Start", "Practice", "Quit"];
+    let mut curr_input = InputBuffer::default();
     let mut start = false; 
     let mut countdown : usize  = 3;
     let mut active_menu_item = MenuItem::Home;
     let mut game_start_time: Option<Instant> = None;
-    let word = new(100);
-    let mut game_words: VecDeque<String> = word.split(' ').map(|w| String::from(w)).collect();
+    let mut game_words: VecDeque<String> = match &mut custom_words {
+        Some(wl) => wl.take(100).into_iter().collect(),
+        None => new(100).split(' ').map(String::from).collect(),
+    };
 
-    let mut score: usize = 0; 
+    let mut score: usize = 0;
+    let mut history = db::load_history()?;
+    let mut run_saved = false;
+    let mut pending_record: Option<RunRecord> = None;
+    let mut next_mode = MenuItem::Game;
+    let mut practice_state = practice::load_state()?;
+    let mut practice_due_pool: VecDeque<String> = VecDeque::new();
+    let mut practice_save_due = false;
+    let mut word_attempt_start = Instant::now();
+    let mut word_backspaces: u32 = 0;
+    let mut typing_stats = TypingStats::default();
+    let mut curr_target_word = String::new();
+    let mut last_wpm: f64 = 0.0;
+    let mut last_accuracy: f64 = 0.0;
+    let mut selected_mode = Mode::Time(60);
+    let mut active_mode = Mode::Time(60);
+    let mut mode_target_words: usize = 0;
 
     loop {
         terminal.draw(|rect| {
@@ -138,79 +223,152 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     .divider(Span::raw("|"));
     
                     rect.render_widget(tabs, chunks[0]);
-                    rect.render_widget(render_home(start, &mut countdown), chunks[1]);
+                    rect.render_widget(render_home(start, &mut countdown, &history, selected_mode), chunks[1]);
                     if countdown == 0 {
-                        active_menu_item = MenuItem::Game;
+                        match next_mode {
+                            MenuItem::Practice => {
+                                practice_state.session += 1;
+                                practice_due_pool = practice::due_words(&practice_state);
+                                game_words = practice::fill_queue(&mut practice_due_pool, 10, &mut custom_words);
+                            }
+                            MenuItem::Game => {
+                                active_mode = selected_mode;
+                                let words = active_mode.starting_words(&mut custom_words);
+                                mode_target_words = match active_mode {
+                                    Mode::Words(count) => count,
+                                    Mode::Quote => words.len(),
+                                    Mode::Time(_) => 0,
+                                };
+                                game_words = words.into_iter().collect();
+                            }
+                            _ => {}
+                        }
+                        active_menu_item = next_mode;
                         game_start_time = Some(Instant::now());
+                        word_attempt_start = Instant::now();
+                        word_backspaces = 0;
+                        typing_stats = TypingStats::default();
+                        curr_target_word = game_words.front().cloned().unwrap_or_default();
                     }
                 },
                 MenuItem::Game => {
-                    let curr_problem = game_words.front().unwrap().as_str();
-                    if curr_input.trim().eq(curr_problem) {
+                    if !game_words.is_empty() {
+                        let curr_problem = game_words.front().unwrap().clone();
+                        if curr_input.as_str().trim().eq(curr_problem.as_str()) {
+                            typing_stats.record_word_complete(curr_problem.graphemes(true).count());
+
+                            game_words.pop_front();
+                            if active_mode.replenishes() {
+                                game_words.push_back(wordlist::next_word(&mut custom_words));
+                            }
+
+                            curr_input.clear();
+                            score += 1;
+                        }
+                    }
+                    curr_target_word = game_words.front().cloned().unwrap_or_default();
+                    let text = render_word_progress(curr_input.as_str(), curr_target_word.as_str());
+                    let input = Paragraph::new(text).block(Block::default().title("Input").borders(Borders::ALL));
+
+                    rect.render_widget(input, chunks[0]);
+
+                    let in_game_timer = game_start_time.unwrap().elapsed().as_secs();
+
+                    let round_end = match active_mode {
+                        Mode::Time(total_secs) => RoundEnd::Timed { elapsed_secs: in_game_timer, total_secs },
+                        Mode::Words(_) | Mode::Quote => RoundEnd::WordCount {
+                            done: score,
+                            target: mode_target_words,
+                            queue_empty: game_words.is_empty(),
+                        },
+                    };
+                    let (percent, progress_title, run_ended) = round_progress(round_end);
+
+                    if run_ended {
+                        active_menu_item = MenuItem::GameOver;
+                        start = false;
+                        if !run_saved {
+                            run_saved = true;
+                            last_wpm = typing_stats.wpm(in_game_timer as f64);
+                            last_accuracy = typing_stats.accuracy();
+                            pending_record = Some(RunRecord {
+                                timestamp: SystemTime::now()
+                                    .duration_since(UNIX_EPOCH)
+                                    .map(|d| d.as_secs())
+                                    .unwrap_or(0),
+                                score,
+                                wpm: last_wpm,
+                                accuracy: last_accuracy,
+                                duration_secs: in_game_timer,
+                                mode: active_mode.to_string(),
+                            });
+                        }
+                    }
+
+                    render_game_area(rect, &chunks, &game_words, percent, progress_title);
+                },
+                MenuItem::Practice => {
+                    let curr_problem = game_words.front().unwrap().clone();
+                    if curr_input.as_str().trim().eq(curr_problem.as_str()) {
+                        let elapsed_secs = word_attempt_start.elapsed().as_secs_f64();
+                        let quality = practice::quality_from_attempt(true, word_backspaces, elapsed_secs);
+                        practice::grade(&mut practice_state, &curr_problem, quality);
+                        typing_stats.record_word_complete(curr_problem.graphemes(true).count());
+
                         game_words.pop_front();
-                        let replacement = new(1);
+                        let replacement = practice::next_word(&mut practice_due_pool, &mut custom_words);
                         game_words.push_back(replacement);
 
                         curr_input.clear();
                         score += 1;
+                        word_backspaces = 0;
+                        word_attempt_start = Instant::now();
                     }
-                    let text = Spans::from(vec![Span::raw(curr_input.as_str())]);
+                    curr_target_word = game_words.front().cloned().unwrap_or_default();
+                    let text = render_word_progress(curr_input.as_str(), curr_target_word.as_str());
                     let input = Paragraph::new(text).block(Block::default().title("Input").borders(Borders::ALL));
 
                     rect.render_widget(input, chunks[0]);
-                    
+
                     let in_game_timer = game_start_time.unwrap().elapsed().as_secs();
 
-                    let mut percent = 100 - ((100.0 * in_game_timer as f32) / 60.0) as i32;
-                    if percent <= 0 {
-                        percent = 0;
+                    let (percent, progress_title, run_ended) =
+                        round_progress(RoundEnd::Timed { elapsed_secs: in_game_timer, total_secs: 60 });
+
+                    if run_ended {
                         active_menu_item = MenuItem::GameOver;
                         start = false;
+                        practice_save_due = true;
+                        if !run_saved {
+                            run_saved = true;
+                            last_wpm = typing_stats.wpm(in_game_timer as f64);
+                            last_accuracy = typing_stats.accuracy();
+                            pending_record = Some(RunRecord {
+                                timestamp: SystemTime::now()
+                                    .duration_since(UNIX_EPOCH)
+                                    .map(|d| d.as_secs())
+                                    .unwrap_or(0),
+                                score,
+                                wpm: last_wpm,
+                                accuracy: last_accuracy,
+                                duration_secs: in_game_timer,
+                                mode: String::from("practice"),
+                            });
+                        }
                     }
-                    let words: Vec<Span> = game_words.iter().map(|w| {
-                        let mut w2 = w.to_owned();
-                        w2.push(' ');
-                        Span::raw(w2)
-                    }).collect();
-
-                    let lines: Vec<Spans> = words
-                    .into_iter()
-                    .chunks(10)
-                    .into_iter()
-                    .map(|chunk| {
-                        let v: Vec<Span> = chunk.collect();
-                        Spans::from(v)
-                    })
-                    .collect();
 
-
-                    rect.render_widget(Paragraph::new(lines), chunks[1]);
-                    let mut time_remaining_text = String::from("Time Remaining: ");
-                    let time_remaining_val = (60 - in_game_timer).to_string();
-                    time_remaining_text.push_str(time_remaining_val.as_str());
-
-                    let progress_bar = Gauge::default()
-                        .block(Block::default()
-                        .borders(Borders::ALL)
-                        .title(time_remaining_text))
-                        .gauge_style(Style::default().fg(Color::White).bg(Color::Black))
-                        .percent(percent as u16);
-                    
-                    rect.render_widget(progress_bar, chunks[2]);
-                    
-                    
+                    render_game_area(rect, &chunks, &game_words, percent, progress_title);
                 },
                 MenuItem::GameOver => {
 
                     if start {
                         active_menu_item = MenuItem::Home;
                         game_words.clear();
-                        let mut new_game = new(100).split(' ').map(|w| String::from(w)).collect::<VecDeque<String>> ();
-                        game_words.append(&mut new_game);
                     }
-                    let wpm_string : String = score.to_string();
-
-                    let game_over_text = Spans::from(vec![Span::raw("Game Over | Press 'r' to restart race | Score: "), Span::raw(wpm_string.as_str())]);
+                    let game_over_text = Spans::from(vec![Span::raw(format!(
+                        "Game Over | Press 'r' to restart race | Score: {} | WPM: {:.1} | Accuracy: {:.0}%",
+                        score, last_wpm, last_accuracy
+                    ))]);
                     let game_over_paragraph = Paragraph::new(game_over_text).block(Block::default().borders(Borders::ALL));
                     rect.render_widget(game_over_paragraph, chunks[0]);
 
@@ -221,23 +379,62 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             };
         })?;
 
+        if let Some(record) = pending_record.take() {
+            history = db::append_run(record)?;
+        }
+        if practice_save_due {
+            practice::save_state(&practice_state)?;
+            practice_save_due = false;
+        }
+
         if start {
             match rx.recv()? {
                 Event::Input(event) => match event.modifiers {
                     KeyModifiers::NONE => {
                         match event.code {
                             KeyCode::Backspace => {
-                                curr_input.pop();
+                                curr_input.delete_grapheme();
+                                if let MenuItem::Practice = active_menu_item {
+                                    word_backspaces += 1;
+                                }
                             }
                             KeyCode::Char(c) => {
+                                let target_grapheme =
+                                    curr_target_word.graphemes(true).nth(curr_input.grapheme_count());
+                                typing_stats.record_keystroke(c, target_grapheme);
                                 curr_input.push(c);
                             }
+                            KeyCode::Enter | KeyCode::Esc => {
+                                if let MenuItem::Practice = active_menu_item {
+                                    if !game_words.is_empty() {
+                                        let elapsed_secs = word_attempt_start.elapsed().as_secs_f64();
+                                        let quality =
+                                            practice::quality_from_attempt(false, word_backspaces, elapsed_secs);
+                                        practice::grade(&mut practice_state, &curr_target_word, quality);
+
+                                        game_words.pop_front();
+                                        let replacement =
+                                            practice::next_word(&mut practice_due_pool, &mut custom_words);
+                                        game_words.push_back(replacement);
+
+                                        curr_input.clear();
+                                        word_backspaces = 0;
+                                        word_attempt_start = Instant::now();
+                                    }
+                                }
+                            }
                             _ => {}
                         }
                     },
-                    KeyModifiers::CONTROL => {
-                        if event.code == KeyCode::Char('a') {
-                            curr_input.clear();
+                    KeyModifiers::CONTROL => match event.code {
+                        KeyCode::Char('a') => curr_input.clear(),
+                        KeyCode::Char('u') => curr_input.clear(),
+                        KeyCode::Char('w') => curr_input.delete_word(),
+                        _ => {}
+                    },
+                    KeyModifiers::ALT => {
+                        if event.code == KeyCode::Backspace {
+                            curr_input.delete_word();
                         }
                     },
                     _ => {}
@@ -249,14 +446,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             match rx.recv()? {
                 Event::Input(event) => match event.code {
                     KeyCode::Char('q') => {
-                        disable_raw_mode()?;
-                        terminal.show_cursor()?;
                         break;
                     }
-                    KeyCode::Char('s') | KeyCode::Char('r') => {
+                    KeyCode::Char('s') => {
+                        start = true;
+                        countdown = 3;
+                        score = 0;
+                        run_saved = false;
+                        next_mode = MenuItem::Game;
+                    }
+                    KeyCode::Char('p') => {
                         start = true;
                         countdown = 3;
                         score = 0;
+                        run_saved = false;
+                        next_mode = MenuItem::Practice;
+                    }
+                    KeyCode::Char('r') => {
+                        start = true;
+                        countdown = 3;
+                        score = 0;
+                        run_saved = false;
+                    }
+                    KeyCode::Char('1') => {
+                        selected_mode = Mode::Time(60);
+                    }
+                    KeyCode::Char('2') => {
+                        selected_mode = Mode::Words(25);
+                    }
+                    KeyCode::Char('3') => {
+                        selected_mode = Mode::Quote;
                     }
                     _ => {}
                 },
@@ -268,7 +487,102 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn render_home<'a>(start: bool, countdown: &mut usize) -> Paragraph<'a> {
+/// How a round measures its own completion: a countdown, or a target word count.
+enum RoundEnd {
+    Timed { elapsed_secs: u64, total_secs: u64 },
+    WordCount { done: usize, target: usize, queue_empty: bool },
+}
+
+// Shared by the Game and Practice arms: turns a round's raw progress into the gauge's percent,
+// its title, and whether the round is over.
+fn round_progress(end: RoundEnd) -> (i32, String, bool) {
+    match end {
+        RoundEnd::Timed { elapsed_secs, total_secs } => {
+            let percent = 100 - ((100.0 * elapsed_secs as f32) / total_secs as f32) as i32;
+            let remaining = total_secs.saturating_sub(elapsed_secs);
+            (percent, format!("Time Remaining: {}", remaining), percent <= 0)
+        }
+        RoundEnd::WordCount { done, target, queue_empty } => {
+            let percent = (100 * done / target.max(1)) as i32;
+            (percent, format!("{} / {} words", done, target), done >= target || queue_empty)
+        }
+    }
+}
+
+// Shared by the Game and Practice arms: renders the upcoming word list and the progress gauge.
+fn render_game_area<B: Backend>(
+    rect: &mut Frame<B>,
+    chunks: &[Rect],
+    game_words: &VecDeque<String>,
+    percent: i32,
+    progress_title: String,
+) {
+    let words: Vec<Span> = game_words
+        .iter()
+        .map(|w| {
+            let mut w2 = w.to_owned();
+            w2.push(' ');
+            Span::raw(w2)
+        })
+        .collect();
+
+    let lines: Vec<Spans> = words
+        .into_iter()
+        .chunks(10)
+        .into_iter()
+        .map(|chunk| {
+            let v: Vec<Span> = chunk.collect();
+            Spans::from(v)
+        })
+        .collect();
+
+    rect.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let progress_bar = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(progress_title))
+        .gauge_style(Style::default().fg(Color::White).bg(Color::Black))
+        .percent(percent.clamp(0, 100) as u16);
+
+    rect.render_widget(progress_bar, chunks[2]);
+}
+
+// Colors each typed grapheme green/red against the target word, with the untyped remainder in
+// gray, and reverses the style of the caret's position so the cursor is visible.
+fn render_word_progress<'a>(curr_input: &str, target: &str) -> Spans<'a> {
+    let caret = curr_input.graphemes(true).count();
+    let mut spans: Vec<Span> = curr_input
+        .graphemes(true)
+        .zip_longest(target.graphemes(true))
+        .enumerate()
+        .map(|(i, pair)| match pair {
+            EitherOrBoth::Both(ic, tc) if ic == tc => {
+                Span::styled(tc.to_owned(), Style::default().fg(Color::Green))
+            }
+            EitherOrBoth::Both(_, tc) => {
+                Span::styled(tc.to_owned(), Style::default().fg(Color::Red))
+            }
+            EitherOrBoth::Left(ic) => Span::styled(ic.to_owned(), Style::default().fg(Color::Red)),
+            EitherOrBoth::Right(tc) => {
+                let mut style = Style::default().fg(Color::Gray);
+                if i == caret {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                Span::styled(tc.to_owned(), style)
+            }
+        })
+        .collect();
+    if caret >= target.graphemes(true).count() {
+        spans.push(Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)));
+    }
+    Spans::from(spans)
+}
+
+fn render_home<'a>(
+    start: bool,
+    countdown: &mut usize,
+    history: &[RunRecord],
+    selected_mode: Mode,
+) -> Paragraph<'a> {
     let mut text = vec![
         Spans::from(vec![Span::raw("")]),
         Spans::from(vec![Span::raw("Welcome")]),
@@ -280,7 +594,25 @@ fn render_home<'a>(start: bool, countdown: &mut usize) -> Paragraph<'a> {
             Style::default().fg(Color::LightBlue),
         )]),
         Spans::from(vec![Span::raw("")]),
+        Spans::from(vec![Span::raw(format!(
+            "Mode: {} (1: 60s  2: 25 words  3: quote)",
+            selected_mode
+        ))]),
+        Spans::from(vec![Span::raw("")]),
     ];
+    match db::best_score(history) {
+        Some(best) => text.push(Spans::from(vec![Span::raw(format!("Best score: {}", best))])),
+        None => text.push(Spans::from(vec![Span::raw("Best score: -")])),
+    }
+    text.push(Spans::from(vec![Span::raw("")]));
+    text.push(Spans::from(vec![Span::raw("Recent runs:")]));
+    for record in history.iter().rev().take(5) {
+        text.push(Spans::from(vec![Span::raw(format!(
+            "score {} | {:.0} wpm | {:.0}% acc",
+            record.score, record.wpm, record.accuracy
+        ))]));
+    }
+    text.push(Spans::from(vec![Span::raw("")]));
     if start && *countdown > 0 {
         thread::sleep(Duration::from_secs(1));
         let mut s = String::from("Starting race in ");
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use crate::wordlist::WordList;
+
+/// A fixed passage used by `Mode::Quote` so every quote run tests the same text.
+pub const QUOTE_TEXT: &str = "the quick brown fox jumps over the lazy dog while the five boxing \
+wizards jump quickly and pack my box with five dozen liquor jugs";
+
+/// The shape of a run: a countdown, a fixed word count, or a fixed passage.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    Time(u64),
+    Words(usize),
+    Quote,
+}
+
+impl Mode {
+    /// The word queue a run of this mode should start with, drawing from `custom_words`
+    /// instead of `rand_word`/`QUOTE_TEXT` when a word list file was loaded. For `Quote`, a
+    /// loaded file is treated as a quote deck (one quote per line) and a random line is drawn.
+    pub fn starting_words(&self, custom_words: &mut Option<WordList>) -> Vec<String> {
+        match self {
+            Mode::Time(_) | Mode::Words(_) => match custom_words {
+                Some(wl) => wl.take(100),
+                None => rand_word::new(100).split(' ').map(String::from).collect(),
+            },
+            Mode::Quote => match custom_words {
+                Some(wl) => wl.next_one().split(' ').map(String::from).collect(),
+                None => QUOTE_TEXT.split(' ').map(String::from).collect(),
+            },
+        }
+    }
+
+    /// Whether a run in this mode keeps pulling fresh random words as they're typed.
+    pub fn replenishes(&self) -> bool {
+        matches!(self, Mode::Time(_) | Mode::Words(_))
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Time(secs) => write!(f, "time({}s)", secs),
+            Mode::Words(count) => write!(f, "words({})", count),
+            Mode::Quote => write!(f, "quote"),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::wordlist::{self, WordList};
+use crate::Error;
+
+/// SM-2 bookkeeping for a single word.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WordStat {
+    pub ef: f64,
+    pub n: u32,
+    pub interval: i64,
+    pub due: i64,
+}
+
+impl Default for WordStat {
+    fn default() -> Self {
+        WordStat {
+            ef: 2.5,
+            n: 0,
+            interval: 0,
+            due: 0,
+        }
+    }
+}
+
+/// Persisted practice progress: the running session counter plus per-word SM-2 state.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PracticeState {
+    pub session: i64,
+    pub words: HashMap<String, WordStat>,
+}
+
+fn practice_path() -> PathBuf {
+    let mut path = dirs::home_dir().unwrap_or_default();
+    path.push(".config");
+    path.push("tui-typing-test");
+    path.push("practice.json");
+    path
+}
+
+/// Loads saved practice progress, defaulting to a fresh state if none exists yet.
+pub fn load_state() -> Result<PracticeState, Error> {
+    let path = practice_path();
+    if !path.exists() {
+        return Ok(PracticeState::default());
+    }
+    let contents = fs::read_to_string(&path)?;
+    if contents.trim().is_empty() {
+        return Ok(PracticeState::default());
+    }
+    let state: PracticeState = serde_json::from_str(&contents)?;
+    Ok(state)
+}
+
+pub fn save_state(state: &PracticeState) -> Result<(), Error> {
+    let path = practice_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let serialized = serde_json::to_string_pretty(state)?;
+    fs::write(&path, serialized)?;
+    Ok(())
+}
+
+/// Grades a typed word on the SM-2 0-5 scale and reschedules its next due session.
+pub fn grade(state: &mut PracticeState, word: &str, quality: u8) {
+    let stat = state.words.entry(word.to_string()).or_insert_with(WordStat::default);
+
+    if quality < 3 {
+        stat.n = 0;
+        stat.interval = 1;
+    } else {
+        stat.n += 1;
+        stat.interval = match stat.n {
+            1 => 1,
+            2 => 6,
+            _ => (stat.interval as f64 * stat.ef).round() as i64,
+        };
+    }
+
+    let q = quality as f64;
+    stat.ef = (stat.ef + 0.1 - (5.0 - q) * (0.08 + (5.0 - q) * 0.02)).max(1.3);
+    stat.due = state.session + stat.interval;
+}
+
+/// Grades typing quality from correctness, backspace count, and how long the word took.
+pub fn quality_from_attempt(correct: bool, backspaces: u32, elapsed_secs: f64) -> u8 {
+    if !correct {
+        return if backspaces == 0 { 0 } else { 2 };
+    }
+    if backspaces == 0 && elapsed_secs < 3.0 {
+        5
+    } else if backspaces == 0 {
+        4
+    } else {
+        3
+    }
+}
+
+/// All words due for review this session, sorted soonest-due-first. Drained incrementally over
+/// the course of the session (see `next_word`) rather than consumed all at once, so due words
+/// keep resurfacing after the round's initial queue instead of giving way to random words.
+pub fn due_words(state: &PracticeState) -> VecDeque<String> {
+    let mut due: Vec<(&String, &WordStat)> = state
+        .words
+        .iter()
+        .filter(|(_, stat)| stat.due <= state.session)
+        .collect();
+    due.sort_by_key(|(_, stat)| stat.due);
+    due.into_iter().map(|(word, _)| word.clone()).collect()
+}
+
+/// Fills a round's starting word queue from `due_pool`, topping up with fresh words when the
+/// due pool runs short.
+pub fn fill_queue(
+    due_pool: &mut VecDeque<String>,
+    target_len: usize,
+    custom_words: &mut Option<WordList>,
+) -> VecDeque<String> {
+    let mut queue = VecDeque::new();
+    while queue.len() < target_len {
+        queue.push_back(next_word(due_pool, custom_words));
+    }
+    queue
+}
+
+/// The next word to drill: a still-due word from `due_pool` if any remain this session,
+/// otherwise a fresh word from the custom list / `rand_word`.
+pub fn next_word(due_pool: &mut VecDeque<String>, custom_words: &mut Option<WordList>) -> String {
+    due_pool
+        .pop_front()
+        .unwrap_or_else(|| wordlist::next_word(custom_words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_correct_grade_sets_interval_one() {
+        let mut state = PracticeState::default();
+        grade(&mut state, "hello", 5);
+        let stat = &state.words["hello"];
+        assert_eq!(stat.n, 1);
+        assert_eq!(stat.interval, 1);
+        assert_eq!(stat.due, 1);
+    }
+
+    #[test]
+    fn repeated_correct_grades_grow_the_interval() {
+        let mut state = PracticeState::default();
+        grade(&mut state, "hello", 5); // n=1, interval=1
+        grade(&mut state, "hello", 5); // n=2, interval=6
+        grade(&mut state, "hello", 5); // n=3, interval=round(6*EF)
+        let stat = &state.words["hello"];
+        assert_eq!(stat.n, 3);
+        assert!(stat.interval > 6, "interval should keep growing past the n=2 step");
+    }
+
+    #[test]
+    fn a_failed_grade_resets_repetition_and_interval() {
+        let mut state = PracticeState::default();
+        grade(&mut state, "hello", 5);
+        grade(&mut state, "hello", 5);
+        grade(&mut state, "hello", 1); // quality < 3: lapse
+        let stat = &state.words["hello"];
+        assert_eq!(stat.n, 0);
+        assert_eq!(stat.interval, 1);
+    }
+
+    #[test]
+    fn easiness_factor_never_drops_below_the_sm2_floor() {
+        let mut state = PracticeState::default();
+        for _ in 0..20 {
+            grade(&mut state, "hello", 0);
+        }
+        assert!(state.words["hello"].ef >= 1.3);
+    }
+
+    #[test]
+    fn due_words_only_returns_words_due_this_session() {
+        let mut state = PracticeState::default();
+        state.session = 5;
+        state.words.insert("due-now".into(), WordStat { due: 5, ..WordStat::default() });
+        state.words.insert("overdue".into(), WordStat { due: 2, ..WordStat::default() });
+        state.words.insert("not-yet".into(), WordStat { due: 9, ..WordStat::default() });
+
+        let due: Vec<String> = due_words(&state).into_iter().collect();
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[0], "overdue"); // soonest-due first
+        assert_eq!(due[1], "due-now");
+    }
+}
@@ -0,0 +1,35 @@
+/// Accumulates keystroke-level accuracy and correctly-typed character count for a run,
+/// so WPM and accuracy reflect what was actually typed instead of just words completed.
+#[derive(Default)]
+pub struct TypingStats {
+    total_keystrokes: usize,
+    correct_keystrokes: usize,
+    correct_chars: usize,
+}
+
+impl TypingStats {
+    pub fn record_keystroke(&mut self, typed: char, target: Option<&str>) {
+        self.total_keystrokes += 1;
+        if target == Some(typed.to_string().as_str()) {
+            self.correct_keystrokes += 1;
+        }
+    }
+
+    pub fn record_word_complete(&mut self, word_len: usize) {
+        self.correct_chars += word_len;
+    }
+
+    pub fn wpm(&self, elapsed_secs: f64) -> f64 {
+        if elapsed_secs <= 0.0 {
+            return 0.0;
+        }
+        (self.correct_chars as f64 / 5.0) / (elapsed_secs / 60.0)
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.total_keystrokes == 0 {
+            return 100.0;
+        }
+        100.0 * self.correct_keystrokes as f64 / self.total_keystrokes as f64
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+use crate::Error;
+
+/// A shuffled, cycling pool of words/quotes loaded from a user-supplied plain-text file
+/// (one entry per line), used in place of `rand_word::new` when given.
+pub struct WordList {
+    entries: Vec<String>,
+    cursor: usize,
+}
+
+impl WordList {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)?;
+        let mut entries: Vec<String> = contents
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        if entries.is_empty() {
+            return Err(Error::from(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} has no usable words/quotes (every line is blank)", path.display()),
+            )));
+        }
+        entries.shuffle(&mut thread_rng());
+        Ok(WordList { entries, cursor: 0 })
+    }
+
+    /// Pulls `count` entries from the pool, reshuffling and cycling back to the start once exhausted.
+    pub fn take(&mut self, count: usize) -> Vec<String> {
+        (0..count).map(|_| self.next_one()).collect()
+    }
+
+    pub fn next_one(&mut self) -> String {
+        if self.entries.is_empty() {
+            return String::new();
+        }
+        if self.cursor >= self.entries.len() {
+            self.entries.shuffle(&mut thread_rng());
+            self.cursor = 0;
+        }
+        let word = self.entries[self.cursor].clone();
+        self.cursor += 1;
+        word
+    }
+}
+
+/// Draws the next random word, from the custom pool if one is loaded, otherwise `rand_word`.
+pub fn next_word(custom_words: &mut Option<WordList>) -> String {
+    match custom_words {
+        Some(wl) => wl.next_one(),
+        None => rand_word::new(1),
+    }
+}